@@ -1,31 +1,107 @@
 use iced::{
-    widget::{button, column, container, row, text, image as iced_image},
+    widget::{button, column, container, row, text, text_input, image as iced_image},
     Application, Command, Element, Settings, Theme, Length,
     executor, window, keyboard, event, subscription,
 };
-use std::{path::PathBuf, collections::HashMap};
+use std::{path::PathBuf, collections::{HashMap, VecDeque}};
 use walkdir::WalkDir;
 use image::open as image_open;
 use rayon::prelude::*;
 use rfd;
+use trash;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use iced::futures::{channel::mpsc, SinkExt, StreamExt};
 
 struct PhotoSelector {
     photo_paths: Vec<PathBuf>,
-    cached_photos: HashMap<usize, Photo>,
+    cached_photos: HashMap<PathBuf, Photo>,
     selected_photos: HashMap<PathBuf, bool>,
     current_photo_index: usize,
     loading_file: Option<String>,
+    undo_stack: Vec<(usize, PathBuf)>,
+    photo_hashes: HashMap<PathBuf, u64>,
+    duplicate_groups: Vec<Vec<PathBuf>>,
+    duplicate_threshold: u32,
+    duplicate_group_index: usize,
+    viewing_duplicates: bool,
+    current_folder: Option<PathBuf>,
+    cache_access_order: VecDeque<PathBuf>,
+    active_extensions: Vec<String>,
+    export_mode: ExportMode,
+    search_active: bool,
+    search_query: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportMode {
+    Copy,
+    Move,
+}
+
+const DEFAULT_DUPLICATE_THRESHOLD: u32 = 10;
+const CACHE_CAPACITY: usize = 15;
+const PREFETCH_RADIUS: usize = 3;
+const ALL_SUPPORTED_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "tiff", "bmp", "cr2", "nef", "arw", "dng"];
+const RAW_EXTENSIONS: &[&str] = &["cr2", "nef", "arw", "dng"];
+
 impl PhotoSelector {
-    fn cleanup_cache(&mut self) {
-        let keep_indices: Vec<usize> = vec![
-            self.current_photo_index.saturating_sub(1),
-            self.current_photo_index,
-            self.current_photo_index.saturating_add(1)
-        ];
-        
-        self.cached_photos.retain(|&index, _| keep_indices.contains(&index));
+    fn touch_cache(&mut self, path: PathBuf) {
+        self.cache_access_order.retain(|p| p != &path);
+        self.cache_access_order.push_back(path);
+        self.evict_cache();
+    }
+
+    fn evict_cache(&mut self) {
+        let current_path = self.photo_paths.get(self.current_photo_index).cloned();
+        while self.cached_photos.len() > CACHE_CAPACITY {
+            match self.cache_access_order.iter().position(|p| Some(p) != current_path.as_ref()) {
+                Some(position) => {
+                    let lru_path = self.cache_access_order.remove(position).unwrap();
+                    self.cached_photos.remove(&lru_path);
+                }
+                None => break,
+            }
+        }
+    }
+
+    fn prefetch_command(&self) -> Command<Message> {
+        let mut commands = Vec::new();
+
+        for offset in 1..=PREFETCH_RADIUS {
+            let next_index = self.current_photo_index + offset;
+            if let Some(path) = self.photo_paths.get(next_index) {
+                if !self.cached_photos.contains_key(path) {
+                    commands.push(Command::perform(load_single_photo(path.clone()), Message::PhotoLoaded));
+                }
+            }
+
+            if let Some(previous_index) = self.current_photo_index.checked_sub(offset) {
+                if let Some(path) = self.photo_paths.get(previous_index) {
+                    if !self.cached_photos.contains_key(path) {
+                        commands.push(Command::perform(load_single_photo(path.clone()), Message::PhotoLoaded));
+                    }
+                }
+            }
+        }
+
+        Command::batch(commands)
+    }
+
+    fn jump_to_photo(&mut self, index: usize) -> Command<Message> {
+        if index >= self.photo_paths.len() {
+            return Command::none();
+        }
+
+        self.search_active = false;
+        self.search_query.clear();
+        self.current_photo_index = index;
+        let path = self.photo_paths[index].clone();
+        self.touch_cache(path.clone());
+
+        Command::batch(vec![
+            Command::perform(load_single_photo(path), Message::PhotoLoaded),
+            self.prefetch_command(),
+        ])
     }
 }
 
@@ -39,15 +115,31 @@ struct Photo {
 #[derive(Debug, Clone)]
 enum Message {
     LoadPhotoPaths(Vec<PathBuf>),
-    PhotoLoaded((usize, Photo)),
+    PhotoLoaded(Photo),
     NextPhoto,
     PreviousPhoto,
     SelectPhoto,
     DeletePhoto(usize),
+    UndoDelete,
     KeyPressed(keyboard::Event),
     LoadingStatus(String),
     OpenFolderDialog,
     FolderSelected(PathBuf),
+    PhotoHashesComputed(HashMap<PathBuf, u64>),
+    FindDuplicates,
+    KeepDuplicate(PathBuf),
+    FolderChanged,
+    PathsRefreshed(Vec<PathBuf>),
+    ToggleExtension(String),
+    AdjustDuplicateThreshold(i32),
+    ExportSelected,
+    ExportDestinationSelected(Option<PathBuf>),
+    PhotosExported(Vec<Result<PathBuf, String>>),
+    ToggleExportMode,
+    ToggleSearch,
+    SearchQueryChanged(String),
+    SearchSubmit,
+    JumpToPhoto(usize),
 }
 
 impl Application for PhotoSelector {
@@ -64,6 +156,18 @@ impl Application for PhotoSelector {
                 selected_photos: HashMap::new(),
                 current_photo_index: 0,
                 loading_file: None,
+                undo_stack: Vec::new(),
+                photo_hashes: HashMap::new(),
+                duplicate_groups: Vec::new(),
+                duplicate_threshold: DEFAULT_DUPLICATE_THRESHOLD,
+                duplicate_group_index: 0,
+                viewing_duplicates: false,
+                current_folder: None,
+                cache_access_order: VecDeque::new(),
+                active_extensions: ALL_SUPPORTED_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+                export_mode: ExportMode::Copy,
+                search_active: false,
+                search_query: String::new(),
             },
             Command::perform(
                 async {
@@ -86,13 +190,20 @@ impl Application for PhotoSelector {
     }
 
     fn subscription(&self) -> iced::Subscription<Message> {
-        subscription::events_with(|event, _| {
+        let keyboard_subscription = subscription::events_with(|event, _| {
             if let event::Event::Keyboard(key_event) = event {
                 Some(Message::KeyPressed(key_event))
             } else {
                 None
             }
-        })
+        });
+
+        let folder_subscription = match &self.current_folder {
+            Some(folder) => watch_folder(folder.clone()),
+            None => iced::Subscription::none(),
+        };
+
+        iced::Subscription::batch(vec![keyboard_subscription, folder_subscription])
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
@@ -100,106 +211,75 @@ impl Application for PhotoSelector {
             Message::LoadPhotoPaths(paths) => {
                 self.photo_paths = paths;
                 if !self.photo_paths.is_empty() {
-                    Command::batch(
-                        self.photo_paths.iter()
-                            .take(10)
-                            .enumerate()
-                            .map(|(i, path)| {
-                                let filename = path.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or("unknown")
-                                    .to_string();
-                                Command::batch(vec![
-                                    Command::perform(
-                                        async move { filename.clone() },
-                                        Message::LoadingStatus
-                                    ),
-                                    Command::perform(
-                                        load_single_photo(path.clone(), i),
-                                        Message::PhotoLoaded
-                                    )
-                                ])
-                            })
-                            .collect::<Vec<_>>()
-                    )
+                    let mut commands = self.photo_paths.iter()
+                        .take(10)
+                        .map(|path| {
+                            let filename = path.file_name()
+                                .and_then(|n| n.to_str())
+                                .unwrap_or("unknown")
+                                .to_string();
+                            Command::batch(vec![
+                                Command::perform(
+                                    async move { filename.clone() },
+                                    Message::LoadingStatus
+                                ),
+                                Command::perform(
+                                    load_single_photo(path.clone()),
+                                    Message::PhotoLoaded
+                                )
+                            ])
+                        })
+                        .collect::<Vec<_>>();
+
+                    commands.push(Command::perform(
+                        compute_photo_hashes(self.photo_paths.clone()),
+                        Message::PhotoHashesComputed
+                    ));
+
+                    Command::batch(commands)
                 } else {
                     Command::none()
                 }
             }
-            Message::PhotoLoaded((index, photo)) => {
-                self.cached_photos.insert(index, photo);
+            Message::PhotoLoaded(photo) => {
+                let path = photo.path.clone();
+                self.cached_photos.insert(path.clone(), photo);
+                self.touch_cache(path);
                 Command::none()
             }
             Message::NextPhoto => {
                 if self.current_photo_index < self.photo_paths.len() - 1 {
                     self.current_photo_index += 1;
-                    self.cleanup_cache();
-
-                    let current_batch = self.current_photo_index / 10;
-                    let position_in_batch = self.current_photo_index % 10;
-                    
-                    if position_in_batch >= 5 {
-                        let next_batch_start = (current_batch + 1) * 10;
-                        
-                        if next_batch_start < self.photo_paths.len() && 
-                           !self.cached_photos.contains_key(&next_batch_start) {
-                            return Command::batch(
-                                self.photo_paths.iter()
-                                    .skip(next_batch_start)
-                                    .take(10)
-                                    .enumerate()
-                                    .map(|(i, path)| {
-                                        let actual_index = next_batch_start + i;
-                                        let filename = path.file_name()
-                                            .and_then(|n| n.to_str())
-                                            .unwrap_or("unknown")
-                                            .to_string();
-                                        Command::batch(vec![
-                                            Command::perform(
-                                                async move { filename.clone() },
-                                                Message::LoadingStatus
-                                            ),
-                                            Command::perform(
-                                                load_single_photo(path.clone(), actual_index),
-                                                Message::PhotoLoaded
-                                            )
-                                        ])
-                                    })
-                                    .collect::<Vec<_>>()
-                            );
-                        }
-                    }
+                    let path = self.photo_paths[self.current_photo_index].clone();
+                    self.touch_cache(path.clone());
+                    return self.prefetch_command();
                 }
                 Command::none()
             }
             Message::PreviousPhoto => {
                 if self.current_photo_index > 0 {
                     self.current_photo_index -= 1;
-                    self.cleanup_cache();
-                    Command::perform(
-                        load_single_photo(
-                            self.photo_paths[self.current_photo_index].clone(),
-                            self.current_photo_index
-                        ),
-                        Message::PhotoLoaded
-                    )
+                    let path = self.photo_paths[self.current_photo_index].clone();
+                    self.touch_cache(path.clone());
+                    Command::batch(vec![
+                        Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                        self.prefetch_command(),
+                    ])
                 } else {
                     Command::none()
                 }
             }
             Message::SelectPhoto => {
-                if let Some(photo) = self.cached_photos.get(&self.current_photo_index) {
+                if let Some(photo) = self.photo_paths.get(self.current_photo_index).and_then(|p| self.cached_photos.get(p)) {
                     self.selected_photos.insert(photo.path.clone(), true);
                     if self.current_photo_index < self.photo_paths.len() - 1 {
                         self.current_photo_index += 1;
-                        self.cleanup_cache();
-                        return Command::perform(
-                            load_single_photo(
-                                self.photo_paths[self.current_photo_index].clone(),
-                                self.current_photo_index
-                            ),
-                            Message::PhotoLoaded
-                        );
+                        let path = self.photo_paths[self.current_photo_index].clone();
+                        self.touch_cache(path.clone());
+                        return Command::batch(vec![
+                            Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                            self.prefetch_command(),
+                        ]);
                     }
                 }
                 Command::none()
@@ -207,60 +287,149 @@ impl Application for PhotoSelector {
             Message::DeletePhoto(index) => {
                 if index < self.photo_paths.len() {
                     let path_to_delete = self.photo_paths[index].clone();
-                    if let Err(e) = std::fs::remove_file(&path_to_delete) {
-                        eprintln!("Failed to delete JPG file: {}", e);
+                    match trash::delete(&path_to_delete) {
+                        Ok(()) => {
+                            self.photo_hashes.remove(&path_to_delete);
+                            self.undo_stack.push((index, path_to_delete.clone()));
+                            self.photo_paths.remove(index);
+                            self.cached_photos.remove(&path_to_delete);
+                            self.cache_access_order.retain(|p| p != &path_to_delete);
+                            if self.current_photo_index >= self.photo_paths.len() {
+                                self.current_photo_index = self.photo_paths.len().saturating_sub(1);
+                            }
+                            if let Some(path) = self.photo_paths.get(self.current_photo_index).cloned() {
+                                return Command::batch(vec![
+                                    Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                                    self.prefetch_command(),
+                                ]);
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to move photo to trash: {}", e);
+                        }
                     }
-                    self.photo_paths.remove(index);
-                    self.cached_photos.remove(&index);
-                    if self.current_photo_index >= self.photo_paths.len() {
-                        self.current_photo_index = self.photo_paths.len().saturating_sub(1);
+                }
+                Command::none()
+            }
+            Message::UndoDelete => {
+                if let Some((index, path)) = self.undo_stack.pop() {
+                    if let Err(e) = restore_trashed_photo(&path) {
+                        eprintln!("Failed to restore photo from trash: {}", e);
+                        return Command::none();
                     }
+                    let insert_at = index.min(self.photo_paths.len());
+                    self.photo_paths.insert(insert_at, path.clone());
+                    self.current_photo_index = insert_at;
+                    self.touch_cache(path.clone());
+                    return Command::batch(vec![
+                        Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                        self.prefetch_command(),
+                    ]);
+                }
+                Command::none()
+            }
+            Message::PhotoHashesComputed(hashes) => {
+                self.photo_hashes.extend(hashes);
+                Command::none()
+            }
+            Message::FindDuplicates => {
+                self.duplicate_groups = group_duplicates(&self.photo_hashes, self.duplicate_threshold);
+                self.duplicate_group_index = 0;
+                self.viewing_duplicates = !self.duplicate_groups.is_empty();
+                Command::none()
+            }
+            Message::AdjustDuplicateThreshold(delta) => {
+                self.duplicate_threshold = (self.duplicate_threshold as i32 + delta).clamp(0, 64) as u32;
+                Command::none()
+            }
+            Message::KeepDuplicate(keep_path) => {
+                if let Some(group) = self.duplicate_groups.get(self.duplicate_group_index).cloned() {
+                    for path in group.iter().filter(|p| **p != keep_path) {
+                        if let Some(index) = self.photo_paths.iter().position(|existing| existing == path) {
+                            let path_to_delete = self.photo_paths[index].clone();
+                            match trash::delete(&path_to_delete) {
+                                Ok(()) => {
+                                    self.undo_stack.push((index, path_to_delete.clone()));
+                                    self.photo_paths.remove(index);
+                                    self.cached_photos.remove(&path_to_delete);
+                                    self.cache_access_order.retain(|p| p != &path_to_delete);
+                                    self.photo_hashes.remove(&path_to_delete);
+                                    if self.current_photo_index >= self.photo_paths.len() {
+                                        self.current_photo_index = self.photo_paths.len().saturating_sub(1);
+                                    }
+                                }
+                                Err(e) => {
+                                    eprintln!("Failed to move duplicate to trash: {}", e);
+                                }
+                            }
+                        }
+                    }
+                }
+                self.duplicate_group_index += 1;
+                if self.duplicate_group_index >= self.duplicate_groups.len() {
+                    self.viewing_duplicates = false;
+                }
+                if let Some(path) = self.photo_paths.get(self.current_photo_index).cloned() {
+                    return Command::batch(vec![
+                        Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                        self.prefetch_command(),
+                    ]);
                 }
                 Command::none()
             }
             Message::KeyPressed(event) => {
                 match event {
                     keyboard::Event::KeyPressed { key_code, .. } => {
+                        if key_code == keyboard::KeyCode::Slash {
+                            self.search_active = !self.search_active;
+                            if !self.search_active {
+                                self.search_query.clear();
+                            }
+                            return Command::none();
+                        }
+
+                        if self.search_active {
+                            if key_code == keyboard::KeyCode::Escape {
+                                self.search_active = false;
+                                self.search_query.clear();
+                            }
+                            return Command::none();
+                        }
+
                         match key_code {
                             keyboard::KeyCode::Left => {
                                 if self.current_photo_index > 0 {
                                     self.current_photo_index -= 1;
-                                    self.cleanup_cache();
-                                    return Command::perform(
-                                        load_single_photo(
-                                            self.photo_paths[self.current_photo_index].clone(),
-                                            self.current_photo_index
-                                        ),
-                                        Message::PhotoLoaded
-                                    );
+                                    let path = self.photo_paths[self.current_photo_index].clone();
+                                    self.touch_cache(path.clone());
+                                    return Command::batch(vec![
+                                        Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                                        self.prefetch_command(),
+                                    ]);
                                 }
                             }
                             keyboard::KeyCode::Right => {
                                 if self.current_photo_index < self.photo_paths.len() - 1 {
                                     self.current_photo_index += 1;
-                                    self.cleanup_cache();
-                                    return Command::perform(
-                                        load_single_photo(
-                                            self.photo_paths[self.current_photo_index].clone(),
-                                            self.current_photo_index
-                                        ),
-                                        Message::PhotoLoaded
-                                    );
+                                    let path = self.photo_paths[self.current_photo_index].clone();
+                                    self.touch_cache(path.clone());
+                                    return Command::batch(vec![
+                                        Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                                        self.prefetch_command(),
+                                    ]);
                                 }
                             }
                             keyboard::KeyCode::S => {
-                                if let Some(photo) = self.cached_photos.get(&self.current_photo_index) {
+                                if let Some(photo) = self.photo_paths.get(self.current_photo_index).and_then(|p| self.cached_photos.get(p)) {
                                     self.selected_photos.insert(photo.path.clone(), true);
                                     if self.current_photo_index < self.photo_paths.len() - 1 {
                                         self.current_photo_index += 1;
-                                        self.cleanup_cache();
-                                        return Command::perform(
-                                            load_single_photo(
-                                                self.photo_paths[self.current_photo_index].clone(),
-                                                self.current_photo_index
-                                            ),
-                                            Message::PhotoLoaded
-                                        );
+                                        let path = self.photo_paths[self.current_photo_index].clone();
+                                        self.touch_cache(path.clone());
+                                        return Command::batch(vec![
+                                            Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                                            self.prefetch_command(),
+                                        ]);
                                     }
                                 }
                             }
@@ -273,6 +442,12 @@ impl Application for PhotoSelector {
                                     );
                                 }
                             }
+                            keyboard::KeyCode::U => {
+                                return Command::perform(
+                                    async {},
+                                    |_| Message::UndoDelete
+                                );
+                            }
                             _ => {}
                         }
                     }
@@ -300,11 +475,179 @@ impl Application for PhotoSelector {
                 )
             }
             Message::FolderSelected(folder_path) => {
+                self.current_folder = Some(folder_path.clone());
                 Command::perform(
-                    load_photo_paths_from(folder_path),
+                    load_photo_paths_from(folder_path, self.active_extensions.clone()),
                     Message::LoadPhotoPaths
                 )
             }
+            Message::FolderChanged => {
+                if let Some(folder) = self.current_folder.clone() {
+                    return Command::perform(
+                        load_photo_paths_from(folder, self.active_extensions.clone()),
+                        Message::PathsRefreshed
+                    );
+                }
+                Command::none()
+            }
+            Message::PathsRefreshed(new_paths) => {
+                let new_set: std::collections::HashSet<&PathBuf> = new_paths.iter().collect();
+                let old_set: std::collections::HashSet<&PathBuf> = self.photo_paths.iter().collect();
+
+                let added: Vec<PathBuf> = new_paths.iter()
+                    .filter(|path| !old_set.contains(path))
+                    .cloned()
+                    .collect();
+                let anything_removed = self.photo_paths.iter().any(|path| !new_set.contains(path));
+
+                if added.is_empty() && !anything_removed {
+                    return Command::none();
+                }
+
+                let current_path = self.photo_paths.get(self.current_photo_index).cloned();
+
+                self.photo_paths.retain(|path| new_set.contains(path));
+                self.photo_paths.extend(added.clone());
+                self.photo_paths.par_sort();
+
+                self.photo_hashes.retain(|path, _| new_set.contains(path));
+
+                self.cached_photos.clear();
+                self.cache_access_order.clear();
+                self.current_photo_index = current_path
+                    .as_ref()
+                    .and_then(|path| self.photo_paths.iter().position(|p| p == path))
+                    .unwrap_or(0);
+
+                if self.photo_paths.is_empty() {
+                    return Command::none();
+                }
+
+                return Command::batch(vec![
+                    Command::perform(
+                        compute_photo_hashes(added),
+                        Message::PhotoHashesComputed
+                    ),
+                    Command::perform(
+                        load_single_photo(self.photo_paths[self.current_photo_index].clone()),
+                        Message::PhotoLoaded
+                    ),
+                ]);
+            }
+            Message::ToggleExtension(extension) => {
+                if self.active_extensions.contains(&extension) {
+                    self.active_extensions.retain(|e| e != &extension);
+                } else {
+                    self.active_extensions.push(extension);
+                }
+
+                if let Some(folder) = self.current_folder.clone() {
+                    return Command::perform(
+                        load_photo_paths_from(folder, self.active_extensions.clone()),
+                        Message::PathsRefreshed
+                    );
+                }
+                Command::none()
+            }
+            Message::ExportSelected => {
+                Command::perform(
+                    async {
+                        rfd::AsyncFileDialog::new()
+                            .set_title("Select Export Destination")
+                            .pick_folder()
+                            .await
+                            .map(|folder| folder.path().to_path_buf())
+                    },
+                    Message::ExportDestinationSelected
+                )
+            }
+            Message::ExportDestinationSelected(destination) => {
+                if let Some(destination) = destination {
+                    let selected: Vec<PathBuf> = self.selected_photos.iter()
+                        .filter(|(_, &is_selected)| is_selected)
+                        .map(|(path, _)| path.clone())
+                        .collect();
+
+                    if selected.is_empty() {
+                        return Command::none();
+                    }
+
+                    self.loading_file = Some(format!("Exporting {} photos...", selected.len()));
+
+                    return Command::perform(
+                        export_selected_photos(selected, destination, self.export_mode),
+                        Message::PhotosExported
+                    );
+                }
+                Command::none()
+            }
+            Message::PhotosExported(results) => {
+                self.loading_file = None;
+                let mut exported_indices: Vec<usize> = Vec::new();
+                for result in results {
+                    match result {
+                        Ok(path) => {
+                            self.selected_photos.remove(&path);
+                            if self.export_mode == ExportMode::Move {
+                                if let Some(index) = self.photo_paths.iter().position(|p| p == &path) {
+                                    exported_indices.push(index);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to export photo: {}", e);
+                        }
+                    }
+                }
+                if !exported_indices.is_empty() {
+                    exported_indices.sort_unstable_by(|a, b| b.cmp(a));
+                    exported_indices.dedup();
+                    for index in exported_indices {
+                        let removed_path = self.photo_paths.remove(index);
+                        self.cached_photos.remove(&removed_path);
+                        self.cache_access_order.retain(|p| p != &removed_path);
+                    }
+                    if self.current_photo_index >= self.photo_paths.len() {
+                        self.current_photo_index = self.photo_paths.len().saturating_sub(1);
+                    }
+                    if let Some(path) = self.photo_paths.get(self.current_photo_index).cloned() {
+                        return Command::batch(vec![
+                            Command::perform(load_single_photo(path), Message::PhotoLoaded),
+                            self.prefetch_command(),
+                        ]);
+                    }
+                }
+                Command::none()
+            }
+            Message::ToggleExportMode => {
+                self.export_mode = match self.export_mode {
+                    ExportMode::Copy => ExportMode::Move,
+                    ExportMode::Move => ExportMode::Copy,
+                };
+                Command::none()
+            }
+            Message::ToggleSearch => {
+                self.search_active = !self.search_active;
+                if !self.search_active {
+                    self.search_query.clear();
+                }
+                Command::none()
+            }
+            Message::SearchQueryChanged(query) => {
+                self.search_query = query;
+                Command::none()
+            }
+            Message::SearchSubmit => {
+                let results = search_matches(&self.photo_paths, &self.search_query);
+                if let Some((index, _)) = results.first() {
+                    let index = *index;
+                    return self.jump_to_photo(index);
+                }
+                Command::none()
+            }
+            Message::JumpToPhoto(index) => {
+                self.jump_to_photo(index)
+            }
         }
     }
 
@@ -318,7 +661,62 @@ impl Application for PhotoSelector {
             .into();
         }
 
-        if let Some(photo) = self.cached_photos.get(&self.current_photo_index) {
+        if self.search_active {
+            let results = search_matches(&self.photo_paths, &self.search_query);
+
+            let results_list = column(
+                results.iter()
+                    .take(20)
+                    .map(|(index, path)| {
+                        let filename = path.file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("unknown")
+                            .to_string();
+                        button(text(filename)).on_press(Message::JumpToPhoto(*index)).into()
+                    })
+                    .collect::<Vec<_>>()
+            )
+            .spacing(5);
+
+            return column![
+                text("Jump to photo (Esc to cancel)").size(20),
+                text_input("Search by filename...", &self.search_query)
+                    .on_input(Message::SearchQueryChanged)
+                    .on_submit(Message::SearchSubmit),
+                results_list,
+            ]
+            .spacing(10)
+            .padding(20)
+            .into();
+        }
+
+        if self.viewing_duplicates {
+            if let Some(group) = self.duplicate_groups.get(self.duplicate_group_index) {
+                let mut thumbnails = row![].spacing(10);
+                for path in group {
+                    let filename = path.file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string();
+                    thumbnails = thumbnails.push(
+                        column![
+                            text(filename),
+                            button("Keep this one").on_press(Message::KeepDuplicate(path.clone())),
+                        ]
+                        .spacing(5)
+                    );
+                }
+
+                return column![
+                    text(format!("Duplicate group {}/{}", self.duplicate_group_index + 1, self.duplicate_groups.len())).size(24),
+                    thumbnails,
+                ]
+                .spacing(20)
+                .into();
+            }
+        }
+
+        if let Some(photo) = self.photo_paths.get(self.current_photo_index).and_then(|p| self.cached_photos.get(p)) {
             let total_photos = self.photo_paths.len();
             let current_index = self.current_photo_index + 1;
 
@@ -331,6 +729,7 @@ impl Application for PhotoSelector {
                     row![
                         button("S - Select").on_press(Message::SelectPhoto),
                         button("D - Delete").on_press(Message::DeletePhoto(self.current_photo_index)),
+                        button("U - Undo").on_press(Message::UndoDelete),
                     ],
                     text(format!("{}/{}", current_index, total_photos)).size(20),
                 ]
@@ -338,11 +737,37 @@ impl Application for PhotoSelector {
             )
             .padding(10);
 
+            let extension_toggles = row(
+                ALL_SUPPORTED_EXTENSIONS.iter()
+                    .map(|extension| {
+                        let extension = extension.to_string();
+                        let label = if self.active_extensions.contains(&extension) {
+                            format!("[x] {}", extension)
+                        } else {
+                            format!("[ ] {}", extension)
+                        };
+                        button(text(label)).on_press(Message::ToggleExtension(extension)).into()
+                    })
+                    .collect::<Vec<_>>()
+            )
+            .spacing(10);
+
             column![
                 row![
                     button("← Previous").on_press(Message::PreviousPhoto),
                     button("Next →").on_press(Message::NextPhoto),
+                    button("Find Duplicates").on_press(Message::FindDuplicates),
+                    button("Threshold -").on_press(Message::AdjustDuplicateThreshold(-1)),
+                    text(format!("Threshold: {}", self.duplicate_threshold)),
+                    button("Threshold +").on_press(Message::AdjustDuplicateThreshold(1)),
+                    button("Export Selected").on_press(Message::ExportSelected),
+                    button(text(match self.export_mode {
+                        ExportMode::Copy => "Mode: Copy",
+                        ExportMode::Move => "Mode: Move",
+                    })).on_press(Message::ToggleExportMode),
+                    button("/ - Search").on_press(Message::ToggleSearch),
                 ].spacing(20),
+                extension_toggles,
                 photo_element,
             ]
             .spacing(20)
@@ -367,7 +792,15 @@ impl Application for PhotoSelector {
     }
 }
 
-async fn load_single_photo(path: PathBuf, index: usize) -> (usize, Photo) {
+fn restore_trashed_photo(original_path: &PathBuf) -> Result<(), trash::Error> {
+    let items = trash::os_limited::list()?;
+    if let Some(item) = items.into_iter().find(|item| item.original_path() == *original_path) {
+        trash::os_limited::restore_all(vec![item])?;
+    }
+    Ok(())
+}
+
+async fn load_single_photo(path: PathBuf) -> Photo {
     let _filename = path.file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unknown")
@@ -383,7 +816,19 @@ async fn load_single_photo(path: PathBuf, index: usize) -> (usize, Photo) {
         String::new()
     };
 
-    let handle = if let Ok(img) = image_open(&path) {
+    let extension = path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let is_raw = RAW_EXTENSIONS.contains(&extension.as_str());
+
+    let opened_image = if is_raw {
+        extract_raw_preview(&path)
+    } else {
+        image_open(&path).ok()
+    };
+
+    let handle = if let Some(img) = opened_image {
         let (width, height) = (img.width(), img.height());
         let handle = if width > 1600 || height > 900 {
             let aspect_ratio = width as f32 / height as f32;
@@ -420,11 +865,29 @@ async fn load_single_photo(path: PathBuf, index: usize) -> (usize, Photo) {
         iced::widget::image::Handle::from_pixels(1, 1, vec![0, 0, 0, 255])
     };
 
-    (index, Photo {
+    Photo {
         path,
         exif_data,
         handle,
-    })
+    }
+}
+
+fn extract_raw_preview(path: &PathBuf) -> Option<image::DynamicImage> {
+    let file_bytes = std::fs::read(path).ok()?;
+    let mut cursor = std::io::Cursor::new(&file_bytes);
+    let exif_reader = exif::Reader::new().read_from_container(&mut cursor).ok()?;
+
+    let offset = exif_reader
+        .get_field(exif::Tag::JPEGInterchangeFormat, exif::In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+    let length = exif_reader
+        .get_field(exif::Tag::JPEGInterchangeFormatLength, exif::In::THUMBNAIL)?
+        .value
+        .get_uint(0)? as usize;
+
+    let jpeg_bytes = file_bytes.get(offset..offset + length)?;
+    image::load_from_memory(jpeg_bytes).ok()
 }
 
 fn format_exif_data(exif: &exif::Exif) -> String {
@@ -457,17 +920,15 @@ fn format_exif_data(exif: &exif::Exif) -> String {
     result
 }
 
-async fn load_photo_paths_from(folder_path: PathBuf) -> Vec<PathBuf> {
+async fn load_photo_paths_from(folder_path: PathBuf, active_extensions: Vec<String>) -> Vec<PathBuf> {
     let mut paths = Vec::new();
-    
+
     for entry in WalkDir::new(folder_path).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
         if let Some(extension) = path.extension() {
-            match extension.to_str().unwrap().to_lowercase().as_str() {
-                "jpg" | "jpeg" | "JPG" | "JPEG" => {
-                    paths.push(path.to_path_buf());
-                }
-                _ => {}
+            let extension = extension.to_str().unwrap_or("").to_lowercase();
+            if active_extensions.iter().any(|allowed| *allowed == extension) {
+                paths.push(path.to_path_buf());
             }
         }
     }
@@ -476,6 +937,186 @@ async fn load_photo_paths_from(folder_path: PathBuf) -> Vec<PathBuf> {
     paths
 }
 
+async fn export_selected_photos(
+    paths: Vec<PathBuf>,
+    destination: PathBuf,
+    mode: ExportMode
+) -> Vec<Result<PathBuf, String>> {
+    paths.par_iter()
+        .map(|path| export_single_photo(path, &destination, mode))
+        .collect()
+}
+
+fn export_single_photo(path: &PathBuf, destination: &PathBuf, mode: ExportMode) -> Result<PathBuf, String> {
+    let filename = path.file_name().ok_or_else(|| "photo has no filename".to_string())?;
+    let target = destination.join(filename);
+
+    match mode {
+        ExportMode::Copy => {
+            std::fs::copy(path, &target).map_err(|e| e.to_string())?;
+        }
+        ExportMode::Move => {
+            if std::fs::rename(path, &target).is_err() {
+                // rename fails with EXDEV when source and destination are on
+                // different filesystems (the common case for card/import
+                // folders); fall back to a copy + delete of the original.
+                std::fs::copy(path, &target).map_err(|e| e.to_string())?;
+                std::fs::remove_file(path).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    Ok(path.clone())
+}
+
+fn watch_folder(folder: PathBuf) -> iced::Subscription<Message> {
+    struct FolderWatch;
+
+    subscription::channel(
+        std::any::TypeId::of::<FolderWatch>(),
+        100,
+        move |mut output| async move {
+            let (tx, mut rx) = mpsc::unbounded();
+
+            let mut watcher: RecommendedWatcher = match notify::recommended_watcher(
+                move |res: notify::Result<notify::Event>| {
+                    if res.is_ok() {
+                        let _ = tx.unbounded_send(());
+                    }
+                }
+            ) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    eprintln!("Failed to create folder watcher: {}", e);
+                    std::future::pending::<()>().await;
+                    unreachable!()
+                }
+            };
+
+            if let Err(e) = watcher.watch(&folder, RecursiveMode::NonRecursive) {
+                eprintln!("Failed to watch folder {:?}: {}", folder, e);
+            }
+
+            loop {
+                if rx.next().await.is_some() {
+                    let _ = output.send(Message::FolderChanged).await;
+                }
+            }
+        }
+    )
+}
+
+async fn compute_photo_hashes(paths: Vec<PathBuf>) -> HashMap<PathBuf, u64> {
+    paths
+        .par_iter()
+        .filter_map(|path| compute_dhash(path).map(|hash| (path.clone(), hash)))
+        .collect()
+}
+
+fn compute_dhash(path: &PathBuf) -> Option<u64> {
+    let img = image_open(path).ok()?;
+    let resized = img.grayscale().resize_exact(9, 8, image::imageops::FilterType::Triangle);
+    let pixels = resized.to_luma8();
+
+    let mut hash: u64 = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = pixels.get_pixel(x, y)[0];
+            let right = pixels.get_pixel(x + 1, y)[0];
+            hash <<= 1;
+            if left > right {
+                hash |= 1;
+            }
+        }
+    }
+
+    Some(hash)
+}
+
+fn group_duplicates(hashes: &HashMap<PathBuf, u64>, threshold: u32) -> Vec<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = hashes.keys().cloned().collect();
+    paths.sort();
+
+    let mut visited = vec![false; paths.len()];
+    let mut groups = Vec::new();
+
+    for i in 0..paths.len() {
+        if visited[i] {
+            continue;
+        }
+        let mut group = vec![paths[i].clone()];
+        visited[i] = true;
+        for j in (i + 1)..paths.len() {
+            if visited[j] {
+                continue;
+            }
+            let hash_a = hashes[&paths[i]];
+            let hash_b = hashes[&paths[j]];
+            if (hash_a ^ hash_b).count_ones() < threshold {
+                group.push(paths[j].clone());
+                visited[j] = true;
+            }
+        }
+        if group.len() > 1 {
+            groups.push(group);
+        }
+    }
+
+    groups
+}
+
+fn search_matches(photo_paths: &[PathBuf], query: &str) -> Vec<(usize, PathBuf)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(i32, usize, PathBuf)> = photo_paths.iter()
+        .enumerate()
+        .filter_map(|(i, path)| {
+            let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            fuzzy_score(query, filename).map(|score| (score, i, path.clone()))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, i, path)| (i, path)).collect()
+}
+
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0i32;
+    let mut query_index = 0;
+    let mut last_match_index: Option<usize> = None;
+
+    for (i, &c) in candidate.iter().enumerate() {
+        if query_index >= query.len() {
+            break;
+        }
+        if c != query[query_index] {
+            continue;
+        }
+
+        score += 1;
+        if last_match_index == Some(i.wrapping_sub(1)) {
+            score += 5; // contiguous match
+        }
+        if i == 0 || matches!(candidate[i - 1], '_' | '-' | ' ' | '.') {
+            score += 10; // start-of-word match
+        }
+
+        last_match_index = Some(i);
+        query_index += 1;
+    }
+
+    if query_index == query.len() {
+        Some(score)
+    } else {
+        None
+    }
+}
+
 fn main() -> iced::Result {
     let settings = Settings {
         window: window::Settings {